@@ -0,0 +1,252 @@
+//! Registration, login, and bearer-token auth for the item-mutating routes.
+//!
+//! Passwords are hashed with `argon2` and stored in PHC format; `login`
+//! exchanges a verified password for a short-lived HS256 JWT whose secret
+//! comes from the `JWT_SECRET` env var. [`AuthUser`] is an extractor that
+//! protected handlers take as an argument to require a valid bearer token.
+
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+use axum::{
+    extract::{FromRequestParts, State},
+    http::{request::Parts, StatusCode},
+    Json, RequestPartsExt,
+};
+use axum_extra::{
+    headers::{authorization::Bearer, Authorization},
+    TypedHeader,
+};
+use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{ApiResponse, EmptyResponse};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct User {
+    pub id: Uuid,
+    pub username: String,
+    #[serde(skip)]
+    pub password_hash: String,
+}
+
+pub type UserStore = Arc<RwLock<HashMap<String, User>>>;
+
+/// Creates an empty, shared user store for [`AppState`](crate::AppState).
+pub fn new_store() -> UserStore {
+    Arc::new(RwLock::new(HashMap::new()))
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RegisterRequest {
+    pub username: String,
+    pub password: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct LoginRequest {
+    pub username: String,
+    pub password: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AuthResponse {
+    pub token: String,
+}
+
+/// JWT claims: `sub` is the authenticated user's id, `exp` a Unix timestamp.
+#[derive(Debug, Serialize, Deserialize)]
+struct Claims {
+    sub: Uuid,
+    exp: usize,
+}
+
+const TOKEN_TTL_SECONDS: i64 = 24 * 60 * 60;
+
+/// Reads `JWT_SECRET` for the request path. `main` validates this is set
+/// before the server starts accepting connections, so this only returns
+/// `Err` if that invariant is ever broken; callers turn it into a `500`
+/// rather than panicking mid-request.
+fn jwt_secret() -> Result<String, (StatusCode, Json<ApiResponse<()>>)> {
+    std::env::var("JWT_SECRET").map_err(|_| internal_error("JWT_SECRET is not configured"))
+}
+
+fn unauthorized(message: &str) -> (StatusCode, Json<ApiResponse<()>>) {
+    (
+        StatusCode::UNAUTHORIZED,
+        Json(ApiResponse {
+            success: false,
+            data: None,
+            message: Some(message.to_string()),
+        }),
+    )
+}
+
+fn conflict(message: &str) -> (StatusCode, Json<ApiResponse<()>>) {
+    (
+        StatusCode::CONFLICT,
+        Json(ApiResponse {
+            success: false,
+            data: None,
+            message: Some(message.to_string()),
+        }),
+    )
+}
+
+fn internal_error(message: &str) -> (StatusCode, Json<ApiResponse<()>>) {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ApiResponse {
+            success: false,
+            data: None,
+            message: Some(message.to_string()),
+        }),
+    )
+}
+
+/// Registers a new user, hashing the password with Argon2 before storing it.
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/register",
+    request_body = RegisterRequest,
+    responses(
+        (status = 201, description = "User registered", body = EmptyResponse),
+        (status = 409, description = "Username already taken", body = EmptyResponse)
+    )
+)]
+pub async fn register(
+    State(users): State<UserStore>,
+    Json(payload): Json<RegisterRequest>,
+) -> Result<(StatusCode, Json<ApiResponse<()>>), (StatusCode, Json<ApiResponse<()>>)> {
+    if users.read().unwrap().contains_key(&payload.username) {
+        return Err(conflict(&format!(
+            "username {} is already taken",
+            payload.username
+        )));
+    }
+
+    // Argon2 hashing is CPU-bound and deliberately slow, so it runs on a
+    // blocking thread and outside any lock rather than serializing every
+    // concurrent register/login behind a held write lock.
+    let password = payload.password;
+    let password_hash = tokio::task::spawn_blocking(move || {
+        let salt = SaltString::generate(&mut OsRng);
+        Argon2::default()
+            .hash_password(password.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+    })
+    .await
+    .map_err(|_| internal_error("failed to hash password"))?
+    .map_err(|_| internal_error("failed to hash password"))?;
+
+    let mut users = users.write().unwrap();
+    if users.contains_key(&payload.username) {
+        return Err(conflict(&format!(
+            "username {} is already taken",
+            payload.username
+        )));
+    }
+
+    users.insert(
+        payload.username.clone(),
+        User {
+            id: Uuid::new_v4(),
+            username: payload.username,
+            password_hash,
+        },
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse {
+            success: true,
+            data: None,
+            message: Some("user registered successfully".to_string()),
+        }),
+    ))
+}
+
+/// Verifies a password and issues a signed JWT valid for [`TOKEN_TTL_SECONDS`].
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/login",
+    request_body = LoginRequest,
+    responses(
+        (status = 200, description = "Login succeeded", body = AuthResponse),
+        (status = 401, description = "Invalid credentials", body = EmptyResponse)
+    )
+)]
+pub async fn login(
+    State(users): State<UserStore>,
+    Json(payload): Json<LoginRequest>,
+) -> Result<Json<ApiResponse<AuthResponse>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let user = {
+        let users = users.read().unwrap();
+        users
+            .get(&payload.username)
+            .cloned()
+            .ok_or_else(|| unauthorized("invalid username or password"))?
+    };
+
+    let hash = PasswordHash::new(&user.password_hash).map_err(|_| internal_error("stored password hash is corrupt"))?;
+    Argon2::default()
+        .verify_password(payload.password.as_bytes(), &hash)
+        .map_err(|_| unauthorized("invalid username or password"))?;
+
+    let claims = Claims {
+        sub: user.id,
+        exp: (chrono::Utc::now().timestamp() + TOKEN_TTL_SECONDS) as usize,
+    };
+
+    let token = jsonwebtoken::encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(jwt_secret()?.as_bytes()),
+    )
+    .map_err(|_| internal_error("failed to issue token"))?;
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(AuthResponse { token }),
+        message: None,
+    }))
+}
+
+/// Extractor that requires a valid `Authorization: Bearer <jwt>` header,
+/// yielding the authenticated user's id. Used as a handler argument on
+/// `create_item`, `update_item`, `delete_item`, and `import_items` to gate
+/// mutations.
+pub struct AuthUser {
+    pub user_id: Uuid,
+}
+
+impl<S> FromRequestParts<S> for AuthUser
+where
+    S: Send + Sync,
+{
+    type Rejection = (StatusCode, Json<ApiResponse<()>>);
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let TypedHeader(Authorization(bearer)) = parts
+            .extract::<TypedHeader<Authorization<Bearer>>>()
+            .await
+            .map_err(|_| unauthorized("missing or invalid Authorization header"))?;
+
+        let claims = jsonwebtoken::decode::<Claims>(
+            bearer.token(),
+            &DecodingKey::from_secret(jwt_secret()?.as_bytes()),
+            &Validation::default(),
+        )
+        .map_err(|_| unauthorized("invalid or expired token"))?
+        .claims;
+
+        Ok(AuthUser { user_id: claims.sub })
+    }
+}
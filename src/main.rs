@@ -1,25 +1,37 @@
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    response::IntoResponse,
+    body::Body,
+    extract::{FromRequestParts, Path, Query, Request, State},
+    http::{header, request::Parts, StatusCode},
+    response::{
+        sse::{Event, KeepAlive},
+        IntoResponse, Response, Sse,
+    },
     routing::{delete, get, post, put},
     Json, Router,
 };
+use bytes::Bytes;
+use futures_util::stream::{Stream, StreamExt};
 use serde::{Deserialize, Serialize};
-use std::{
-    collections::HashMap,
-    net::SocketAddr,
-    sync::{Arc, RwLock},
-};
+use std::{collections::VecDeque, convert::Infallible, net::SocketAddr, sync::Arc};
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::BroadcastStream;
 use tower_http::{cors::CorsLayer, trace::TraceLayer};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use utoipa::{OpenApi, ToSchema};
+use utoipa_swagger_ui::SwaggerUi;
 use uuid::Uuid;
 
+mod auth;
+mod repository;
+
+use auth::{AuthUser, UserStore};
+use repository::{Repository, RepositoryError};
+
 // ============================================================================
 // Data Models
 // ============================================================================
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct Item {
     pub id: Uuid,
     pub name: String,
@@ -28,56 +40,185 @@ pub struct Item {
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateItemRequest {
     pub name: String,
     pub description: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct UpdateItemRequest {
     pub name: Option<String>,
     pub description: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
+#[aliases(ItemResponse = ApiResponse<Item>, ItemsPageResponse = ApiResponse<ItemsPage>, EmptyResponse = ApiResponse<()>)]
 pub struct ApiResponse<T> {
     pub success: bool,
     pub data: Option<T>,
     pub message: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct HealthResponse {
     pub status: String,
     pub version: String,
     pub timestamp: chrono::DateTime<chrono::Utc>,
 }
 
+/// Field `list_items` can sort by.
+#[derive(Debug, Clone, Copy, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SortBy {
+    Name,
+    CreatedAt,
+    UpdatedAt,
+}
+
+/// Sort direction for `list_items`.
+#[derive(Debug, Clone, Copy, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+/// Query parameters accepted by `list_items`.
+#[derive(Debug, Deserialize, ToSchema, utoipa::IntoParams)]
+pub struct ListParams {
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+    pub sort_by: Option<SortBy>,
+    pub order: Option<SortOrder>,
+    pub q: Option<String>,
+}
+
+const DEFAULT_LIMIT: u32 = 20;
+const MAX_LIMIT: u32 = 100;
+
+/// A page of items plus the metadata clients need to drive paging UIs.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ItemsPage {
+    pub items: Vec<Item>,
+    pub total: usize,
+    pub limit: u32,
+    pub offset: u32,
+}
+
+/// Notification broadcast to SSE subscribers whenever an item is mutated.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type")]
+pub enum ItemEvent {
+    Created(Item),
+    Updated(Item),
+    Deleted { id: Uuid },
+    /// Sent in place of events a lagging subscriber missed, so it knows to
+    /// re-fetch `list_items` instead of trusting its local view.
+    Resync,
+}
+
 // ============================================================================
 // Application State
 // ============================================================================
 
-type ItemStore = Arc<RwLock<HashMap<Uuid, Item>>>;
+/// Broadcast capacity for the item event stream; subscribers slower than this
+/// many events behind the fastest producer receive a `Resync` hint instead of
+/// the events they missed.
+const EVENT_BUFFER_CAPACITY: usize = 256;
 
 #[derive(Clone)]
 pub struct AppState {
-    items: ItemStore,
+    repository: Arc<dyn Repository>,
+    events: broadcast::Sender<ItemEvent>,
+    users: UserStore,
 }
 
 impl AppState {
-    fn new() -> Self {
+    fn new(repository: Arc<dyn Repository>) -> Self {
+        let (events, _) = broadcast::channel(EVENT_BUFFER_CAPACITY);
         Self {
-            items: Arc::new(RwLock::new(HashMap::new())),
+            repository,
+            events,
+            users: auth::new_store(),
         }
     }
 }
 
+impl axum::extract::FromRef<AppState> for UserStore {
+    fn from_ref(state: &AppState) -> Self {
+        state.users.clone()
+    }
+}
+
+impl From<RepositoryError> for (StatusCode, Json<ApiResponse<()>>) {
+    fn from(err: RepositoryError) -> Self {
+        let message = match &err {
+            RepositoryError::NotFound => err.to_string(),
+            RepositoryError::Backend(_) => {
+                tracing::error!(error = %err, "repository backend error");
+                "internal server error".to_string()
+            }
+        };
+
+        let status = match err {
+            RepositoryError::NotFound => StatusCode::NOT_FOUND,
+            RepositoryError::Backend(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        (
+            status,
+            Json(ApiResponse {
+                success: false,
+                data: None,
+                message: Some(message),
+            }),
+        )
+    }
+}
+
 // ============================================================================
 // Handlers
 // ============================================================================
 
+/// Wraps `axum::extract::Query`, mapping a malformed query string to the same
+/// `ApiResponse<()>` envelope the handlers' own errors use, instead of axum's
+/// default plaintext rejection. Other extractors (`Json`, `Path`) still
+/// reject with axum's defaults.
+pub struct ApiQuery<T>(pub T);
+
+impl<T, S> FromRequestParts<S> for ApiQuery<T>
+where
+    T: serde::de::DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = (StatusCode, Json<ApiResponse<()>>);
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        Query::<T>::from_request_parts(parts, state)
+            .await
+            .map(|Query(value)| ApiQuery(value))
+            .map_err(|rejection| {
+                (
+                    StatusCode::BAD_REQUEST,
+                    Json(ApiResponse {
+                        success: false,
+                        data: None,
+                        message: Some(rejection.to_string()),
+                    }),
+                )
+            })
+    }
+}
+
 /// Health check endpoint
+#[utoipa::path(
+    get,
+    path = "/health",
+    responses(
+        (status = 200, description = "Service is healthy", body = HealthResponse)
+    )
+)]
 async fn health() -> Json<HealthResponse> {
     Json(HealthResponse {
         status: "healthy".to_string(),
@@ -86,47 +227,102 @@ async fn health() -> Json<HealthResponse> {
     })
 }
 
-/// List all items
-async fn list_items(State(state): State<AppState>) -> Json<ApiResponse<Vec<Item>>> {
-    let items = state.items.read().unwrap();
-    let items_vec: Vec<Item> = items.values().cloned().collect();
-    
-    Json(ApiResponse {
+/// List items, optionally paginated, sorted, and filtered by a text query.
+#[utoipa::path(
+    get,
+    path = "/api/v1/items",
+    params(ListParams),
+    responses(
+        (status = 200, description = "A page of items", body = ItemsPageResponse)
+    )
+)]
+async fn list_items(
+    ApiQuery(params): ApiQuery<ListParams>,
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<ItemsPage>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let mut items = state.repository.list().await?;
+
+    if let Some(q) = params.q.as_deref().map(str::to_lowercase).filter(|q| !q.is_empty()) {
+        items.retain(|item| {
+            item.name.to_lowercase().contains(&q)
+                || item
+                    .description
+                    .as_deref()
+                    .is_some_and(|d| d.to_lowercase().contains(&q))
+        });
+    }
+
+    let total = items.len();
+
+    match params.sort_by.unwrap_or(SortBy::CreatedAt) {
+        SortBy::Name => items.sort_by(|a, b| a.name.cmp(&b.name)),
+        SortBy::CreatedAt => items.sort_by_key(|item| item.created_at),
+        SortBy::UpdatedAt => items.sort_by_key(|item| item.updated_at),
+    }
+    if matches!(params.order.unwrap_or(SortOrder::Asc), SortOrder::Desc) {
+        items.reverse();
+    }
+
+    let limit = params.limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT);
+    let offset = params.offset.unwrap_or(0);
+    let page = items
+        .into_iter()
+        .skip(offset as usize)
+        .take(limit as usize)
+        .collect();
+
+    Ok(Json(ApiResponse {
         success: true,
-        data: Some(items_vec),
+        data: Some(ItemsPage {
+            items: page,
+            total,
+            limit,
+            offset,
+        }),
         message: None,
-    })
+    }))
 }
 
 /// Get a single item by ID
+#[utoipa::path(
+    get,
+    path = "/api/v1/items/{id}",
+    params(
+        ("id" = Uuid, Path, description = "Item id")
+    ),
+    responses(
+        (status = 200, description = "Item found", body = ItemResponse),
+        (status = 404, description = "Item not found", body = EmptyResponse)
+    )
+)]
 async fn get_item(
     Path(id): Path<Uuid>,
     State(state): State<AppState>,
 ) -> Result<Json<ApiResponse<Item>>, (StatusCode, Json<ApiResponse<()>>)> {
-    let items = state.items.read().unwrap();
-    
-    match items.get(&id) {
-        Some(item) => Ok(Json(ApiResponse {
-            success: true,
-            data: Some(item.clone()),
-            message: None,
-        })),
-        None => Err((
-            StatusCode::NOT_FOUND,
-            Json(ApiResponse {
-                success: false,
-                data: None,
-                message: Some(format!("Item with id {} not found", id)),
-            }),
-        )),
-    }
+    let item = state.repository.get(id).await?;
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(item),
+        message: None,
+    }))
 }
 
 /// Create a new item
+#[utoipa::path(
+    post,
+    path = "/api/v1/items",
+    request_body = CreateItemRequest,
+    responses(
+        (status = 201, description = "Item created", body = ItemResponse),
+        (status = 401, description = "Missing or invalid bearer token", body = EmptyResponse)
+    )
+)]
 async fn create_item(
+    _auth: AuthUser,
     State(state): State<AppState>,
     Json(payload): Json<CreateItemRequest>,
-) -> (StatusCode, Json<ApiResponse<Item>>) {
+) -> Result<(StatusCode, Json<ApiResponse<Item>>), (StatusCode, Json<ApiResponse<()>>)> {
     let now = chrono::Utc::now();
     let item = Item {
         id: Uuid::new_v4(),
@@ -136,78 +332,254 @@ async fn create_item(
         updated_at: now,
     };
 
-    let mut items = state.items.write().unwrap();
-    items.insert(item.id, item.clone());
+    let item = state.repository.create(item).await?;
+    let _ = state.events.send(ItemEvent::Created(item.clone()));
 
-    (
+    Ok((
         StatusCode::CREATED,
         Json(ApiResponse {
             success: true,
             data: Some(item),
             message: Some("Item created successfully".to_string()),
         }),
-    )
+    ))
 }
 
 /// Update an existing item
+#[utoipa::path(
+    put,
+    path = "/api/v1/items/{id}",
+    params(
+        ("id" = Uuid, Path, description = "Item id")
+    ),
+    request_body = UpdateItemRequest,
+    responses(
+        (status = 200, description = "Item updated", body = ItemResponse),
+        (status = 401, description = "Missing or invalid bearer token", body = EmptyResponse),
+        (status = 404, description = "Item not found", body = EmptyResponse)
+    )
+)]
 async fn update_item(
+    _auth: AuthUser,
     Path(id): Path<Uuid>,
     State(state): State<AppState>,
     Json(payload): Json<UpdateItemRequest>,
 ) -> Result<Json<ApiResponse<Item>>, (StatusCode, Json<ApiResponse<()>>)> {
-    let mut items = state.items.write().unwrap();
+    let updated = state
+        .repository
+        .update(id, payload.name, payload.description)
+        .await?;
 
-    match items.get_mut(&id) {
-        Some(item) => {
-            if let Some(name) = payload.name {
-                item.name = name;
-            }
-            if let Some(description) = payload.description {
-                item.description = Some(description);
-            }
-            item.updated_at = chrono::Utc::now();
+    let _ = state.events.send(ItemEvent::Updated(updated.clone()));
 
-            Ok(Json(ApiResponse {
-                success: true,
-                data: Some(item.clone()),
-                message: Some("Item updated successfully".to_string()),
-            }))
-        }
-        None => Err((
-            StatusCode::NOT_FOUND,
-            Json(ApiResponse {
-                success: false,
-                data: None,
-                message: Some(format!("Item with id {} not found", id)),
-            }),
-        )),
-    }
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(updated),
+        message: Some("Item updated successfully".to_string()),
+    }))
 }
 
 /// Delete an item
+#[utoipa::path(
+    delete,
+    path = "/api/v1/items/{id}",
+    params(
+        ("id" = Uuid, Path, description = "Item id")
+    ),
+    responses(
+        (status = 200, description = "Item deleted", body = EmptyResponse),
+        (status = 401, description = "Missing or invalid bearer token", body = EmptyResponse),
+        (status = 404, description = "Item not found", body = EmptyResponse)
+    )
+)]
 async fn delete_item(
+    _auth: AuthUser,
     Path(id): Path<Uuid>,
     State(state): State<AppState>,
 ) -> Result<Json<ApiResponse<()>>, (StatusCode, Json<ApiResponse<()>>)> {
-    let mut items = state.items.write().unwrap();
+    state.repository.delete(id).await?;
+    let _ = state.events.send(ItemEvent::Deleted { id });
 
-    match items.remove(&id) {
-        Some(_) => Ok(Json(ApiResponse {
-            success: true,
-            data: None,
-            message: Some("Item deleted successfully".to_string()),
-        })),
-        None => Err((
-            StatusCode::NOT_FOUND,
-            Json(ApiResponse {
-                success: false,
-                data: None,
-                message: Some(format!("Item with id {} not found", id)),
-            }),
-        )),
+    Ok(Json(ApiResponse {
+        success: true,
+        data: None,
+        message: Some("Item deleted successfully".to_string()),
+    }))
+}
+
+/// Stream real-time item notifications as Server-Sent Events.
+///
+/// Each subscriber gets its own `broadcast::Receiver`; if it falls too far
+/// behind (`RecvError::Lagged`), the missed events are dropped and a single
+/// `ItemEvent::Resync` is emitted instead of closing the connection, so
+/// clients know to re-fetch `list_items` rather than trusting a gap.
+#[utoipa::path(
+    get,
+    path = "/api/v1/items/events",
+    responses(
+        (status = 200, description = "text/event-stream of ItemEvent payloads")
+    )
+)]
+async fn item_events(
+    State(state): State<AppState>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let stream = BroadcastStream::new(state.events.subscribe()).map(|result| {
+        let event = result.unwrap_or(ItemEvent::Resync);
+        Ok(Event::default().json_data(event).unwrap_or_else(|_| Event::default()))
+    });
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+/// Stream every item as newline-delimited JSON, one serialized item per
+/// chunk, without building the whole response body in memory.
+#[utoipa::path(
+    get,
+    path = "/api/v1/items/export",
+    responses(
+        (status = 200, description = "NDJSON stream, one Item per line", content_type = "application/x-ndjson")
+    )
+)]
+async fn export_items(State(state): State<AppState>) -> Response {
+    let lines = state.repository.stream_all().map(|result| {
+        result.map(|item| {
+            let mut line = serde_json::to_vec(&item).expect("Item always serializes");
+            line.push(b'\n');
+            Bytes::from(line)
+        })
+    });
+
+    (
+        [(header::CONTENT_TYPE, "application/x-ndjson")],
+        Body::from_stream(lines),
+    )
+        .into_response()
+}
+
+/// Reads one `CreateItemRequest` per NDJSON line from the request body as it
+/// arrives, inserting each as it's parsed rather than buffering the upload.
+#[utoipa::path(
+    post,
+    path = "/api/v1/items/import",
+    request_body(content = String, content_type = "application/x-ndjson"),
+    responses(
+        (status = 200, description = "Import summary", body = EmptyResponse),
+        (status = 401, description = "Missing or invalid bearer token", body = EmptyResponse)
+    )
+)]
+async fn import_items(
+    _auth: AuthUser,
+    State(state): State<AppState>,
+    request: Request,
+) -> Result<Json<ApiResponse<()>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let mut body = request.into_body().into_data_stream();
+    // A VecDeque lets us pop completed lines off the front in O(line len)
+    // instead of a Vec's O(remaining buffer len), which would turn a
+    // many-line import into O(total bytes²).
+    let mut buffer: VecDeque<u8> = VecDeque::new();
+    let mut imported = 0usize;
+    let mut rejected = 0usize;
+
+    while let Some(chunk) = body.next().await {
+        let chunk = chunk.map_err(|e| bad_request(&e.to_string()))?;
+        buffer.extend(chunk.iter().copied());
+
+        while let Some(pos) = buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = buffer.drain(..=pos).collect();
+            import_line(&state, &line[..line.len() - 1], &mut imported, &mut rejected).await;
+        }
+    }
+    if !buffer.is_empty() {
+        let line: Vec<u8> = buffer.into_iter().collect();
+        import_line(&state, &line, &mut imported, &mut rejected).await;
+    }
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: None,
+        message: Some(format!("imported {imported} item(s), rejected {rejected}")),
+    }))
+}
+
+/// Parses and inserts a single NDJSON line from `import_items`, counting it
+/// as imported or rejected. Blank lines are ignored without affecting either
+/// count.
+async fn import_line(state: &AppState, line: &[u8], imported: &mut usize, rejected: &mut usize) {
+    if line.iter().all(u8::is_ascii_whitespace) {
+        return;
+    }
+
+    let Ok(payload) = serde_json::from_slice::<CreateItemRequest>(line) else {
+        *rejected += 1;
+        return;
+    };
+
+    let now = chrono::Utc::now();
+    let item = Item {
+        id: Uuid::new_v4(),
+        name: payload.name,
+        description: payload.description,
+        created_at: now,
+        updated_at: now,
+    };
+
+    match state.repository.create(item.clone()).await {
+        Ok(item) => {
+            let _ = state.events.send(ItemEvent::Created(item));
+            *imported += 1;
+        }
+        Err(_) => *rejected += 1,
     }
 }
 
+fn bad_request(message: &str) -> (StatusCode, Json<ApiResponse<()>>) {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(ApiResponse {
+            success: false,
+            data: None,
+            message: Some(message.to_string()),
+        }),
+    )
+}
+
+// ============================================================================
+// OpenAPI Documentation
+// ============================================================================
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        health,
+        list_items,
+        get_item,
+        create_item,
+        update_item,
+        delete_item,
+        item_events,
+        export_items,
+        import_items,
+        auth::register,
+        auth::login
+    ),
+    components(schemas(
+        Item,
+        CreateItemRequest,
+        UpdateItemRequest,
+        HealthResponse,
+        SortBy,
+        SortOrder,
+        ItemsPage,
+        ItemResponse,
+        ItemsPageResponse,
+        EmptyResponse,
+        auth::RegisterRequest,
+        auth::LoginRequest,
+        auth::AuthResponse
+    ))
+)]
+struct ApiDoc;
+
 // ============================================================================
 // Router Setup
 // ============================================================================
@@ -216,12 +588,22 @@ fn create_router(state: AppState) -> Router {
     Router::new()
         // Health check
         .route("/health", get(health))
+        // Auth
+        .route("/api/v1/auth/register", post(auth::register))
+        .route("/api/v1/auth/login", post(auth::login))
         // Items CRUD
         .route("/api/v1/items", get(list_items).post(create_item))
         .route(
             "/api/v1/items/{id}",
             get(get_item).put(update_item).delete(delete_item),
         )
+        // Live updates
+        .route("/api/v1/items/events", get(item_events))
+        // Bulk export/import
+        .route("/api/v1/items/export", get(export_items))
+        .route("/api/v1/items/import", post(import_items))
+        // API documentation
+        .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", ApiDoc::openapi()))
         // Middleware
         .layer(CorsLayer::permissive())
         .layer(TraceLayer::new_for_http())
@@ -243,8 +625,13 @@ async fn main() {
         .with(tracing_subscriber::fmt::layer())
         .init();
 
+    // Fail fast on missing config rather than answering every auth request
+    // with a 500 once traffic starts hitting the auth routes.
+    std::env::var("JWT_SECRET").expect("JWT_SECRET must be set");
+
     // Create application state
-    let state = AppState::new();
+    let repository = repository::from_env().await;
+    let state = AppState::new(repository);
 
     // Build router
     let app = create_router(state);
@@ -0,0 +1,288 @@
+//! Storage backends for [`Item`]s.
+//!
+//! [`AppState`](crate::AppState) holds an `Arc<dyn Repository>` so the
+//! in-memory store used for local development can be swapped for the `sqlx`-backed store in
+//! production without touching any handler.
+
+use async_trait::async_trait;
+use futures_util::stream::{self, Stream};
+use std::{
+    collections::HashMap,
+    pin::Pin,
+    sync::{Arc, RwLock},
+};
+use uuid::Uuid;
+
+use crate::Item;
+
+/// A stream of items yielded one at a time, so callers (e.g. the NDJSON
+/// export handler) never have to hold the whole collection in memory.
+pub type ItemStream = Pin<Box<dyn Stream<Item = Result<Item, RepositoryError>> + Send>>;
+
+/// Errors a [`Repository`] can fail with, independent of any storage engine.
+#[derive(Debug)]
+pub enum RepositoryError {
+    NotFound,
+    Backend(String),
+}
+
+impl std::fmt::Display for RepositoryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RepositoryError::NotFound => write!(f, "item not found"),
+            RepositoryError::Backend(msg) => write!(f, "storage error: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for RepositoryError {}
+
+/// Storage-agnostic access to items, implemented by both the in-memory store
+/// and the `sqlx`-backed store.
+#[async_trait]
+pub trait Repository: Send + Sync {
+    async fn list(&self) -> Result<Vec<Item>, RepositoryError>;
+    async fn get(&self, id: Uuid) -> Result<Item, RepositoryError>;
+    async fn create(&self, item: Item) -> Result<Item, RepositoryError>;
+    async fn update(&self, id: Uuid, name: Option<String>, description: Option<String>) -> Result<Item, RepositoryError>;
+    async fn delete(&self, id: Uuid) -> Result<(), RepositoryError>;
+
+    /// Streams every item one at a time for bulk export. The `sqlx` store
+    /// reads row-by-row without buffering the table; the in-memory store
+    /// yields from a cloned snapshot of its map.
+    fn stream_all(self: Arc<Self>) -> ItemStream;
+}
+
+// ============================================================================
+// In-memory implementation
+// ============================================================================
+
+/// The original `HashMap`-backed store, kept for local development and as
+/// the default when `DATABASE_URL` is unset.
+#[derive(Default)]
+pub struct InMemoryRepository {
+    items: RwLock<HashMap<Uuid, Item>>,
+}
+
+impl InMemoryRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl Repository for InMemoryRepository {
+    async fn list(&self) -> Result<Vec<Item>, RepositoryError> {
+        Ok(self.items.read().unwrap().values().cloned().collect())
+    }
+
+    async fn get(&self, id: Uuid) -> Result<Item, RepositoryError> {
+        self.items
+            .read()
+            .unwrap()
+            .get(&id)
+            .cloned()
+            .ok_or(RepositoryError::NotFound)
+    }
+
+    async fn create(&self, item: Item) -> Result<Item, RepositoryError> {
+        self.items.write().unwrap().insert(item.id, item.clone());
+        Ok(item)
+    }
+
+    async fn update(&self, id: Uuid, name: Option<String>, description: Option<String>) -> Result<Item, RepositoryError> {
+        let mut items = self.items.write().unwrap();
+        let item = items.get_mut(&id).ok_or(RepositoryError::NotFound)?;
+
+        if let Some(name) = name {
+            item.name = name;
+        }
+        if let Some(description) = description {
+            item.description = Some(description);
+        }
+        item.updated_at = chrono::Utc::now();
+
+        Ok(item.clone())
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), RepositoryError> {
+        self.items
+            .write()
+            .unwrap()
+            .remove(&id)
+            .map(|_| ())
+            .ok_or(RepositoryError::NotFound)
+    }
+
+    fn stream_all(self: Arc<Self>) -> ItemStream {
+        let items: Vec<Item> = self.items.read().unwrap().values().cloned().collect();
+        Box::pin(stream::iter(items.into_iter().map(Ok)))
+    }
+}
+
+// ============================================================================
+// sqlx-backed implementation
+// ============================================================================
+
+/// Persists items to SQLite or Postgres, whichever `DATABASE_URL` points at,
+/// via `sqlx`'s database-agnostic `Any` driver.
+pub struct SqlxRepository {
+    pool: sqlx::AnyPool,
+}
+
+impl SqlxRepository {
+    /// Connects to `database_url` and runs the `items` table migration.
+    pub async fn connect(database_url: &str) -> Result<Self, sqlx::Error> {
+        sqlx::any::install_default_drivers();
+        let pool = sqlx::AnyPool::connect(database_url).await?;
+
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS items (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                description TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )",
+        )
+        .execute(&pool)
+        .await?;
+
+        Ok(Self { pool })
+    }
+
+    fn row_to_item(row: &sqlx::any::AnyRow) -> Result<Item, RepositoryError> {
+        use sqlx::Row;
+
+        let id: String = row.try_get("id").map_err(|e| RepositoryError::Backend(e.to_string()))?;
+        let created_at: String = row.try_get("created_at").map_err(|e| RepositoryError::Backend(e.to_string()))?;
+        let updated_at: String = row.try_get("updated_at").map_err(|e| RepositoryError::Backend(e.to_string()))?;
+
+        Ok(Item {
+            id: id.parse().map_err(|_| RepositoryError::Backend("invalid id".into()))?,
+            name: row.try_get("name").map_err(|e| RepositoryError::Backend(e.to_string()))?,
+            description: row.try_get("description").map_err(|e| RepositoryError::Backend(e.to_string()))?,
+            created_at: created_at
+                .parse()
+                .map_err(|_| RepositoryError::Backend("invalid created_at".into()))?,
+            updated_at: updated_at
+                .parse()
+                .map_err(|_| RepositoryError::Backend("invalid updated_at".into()))?,
+        })
+    }
+}
+
+#[async_trait]
+impl Repository for SqlxRepository {
+    async fn list(&self) -> Result<Vec<Item>, RepositoryError> {
+        let rows = sqlx::query("SELECT id, name, description, created_at, updated_at FROM items")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Backend(e.to_string()))?;
+
+        rows.iter().map(Self::row_to_item).collect()
+    }
+
+    async fn get(&self, id: Uuid) -> Result<Item, RepositoryError> {
+        let row = sqlx::query("SELECT id, name, description, created_at, updated_at FROM items WHERE id = $1")
+            .bind(id.to_string())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Backend(e.to_string()))?
+            .ok_or(RepositoryError::NotFound)?;
+
+        Self::row_to_item(&row)
+    }
+
+    async fn create(&self, item: Item) -> Result<Item, RepositoryError> {
+        sqlx::query("INSERT INTO items (id, name, description, created_at, updated_at) VALUES ($1, $2, $3, $4, $5)")
+            .bind(item.id.to_string())
+            .bind(&item.name)
+            .bind(&item.description)
+            .bind(item.created_at.to_rfc3339())
+            .bind(item.updated_at.to_rfc3339())
+            .execute(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Backend(e.to_string()))?;
+
+        Ok(item)
+    }
+
+    async fn update(&self, id: Uuid, name: Option<String>, description: Option<String>) -> Result<Item, RepositoryError> {
+        let mut item = self.get(id).await?;
+
+        if let Some(name) = name {
+            item.name = name;
+        }
+        if let Some(description) = description {
+            item.description = Some(description);
+        }
+        item.updated_at = chrono::Utc::now();
+
+        let result = sqlx::query("UPDATE items SET name = $1, description = $2, updated_at = $3 WHERE id = $4")
+            .bind(&item.name)
+            .bind(&item.description)
+            .bind(item.updated_at.to_rfc3339())
+            .bind(item.id.to_string())
+            .execute(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Backend(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            Err(RepositoryError::NotFound)
+        } else {
+            Ok(item)
+        }
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), RepositoryError> {
+        let result = sqlx::query("DELETE FROM items WHERE id = $1")
+            .bind(id.to_string())
+            .execute(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Backend(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            Err(RepositoryError::NotFound)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn stream_all(self: Arc<Self>) -> ItemStream {
+        use futures_util::TryStreamExt;
+
+        let stream = async_stream::try_stream! {
+            let mut rows = sqlx::query("SELECT id, name, description, created_at, updated_at FROM items")
+                .fetch(&self.pool);
+
+            while let Some(row) = rows
+                .try_next()
+                .await
+                .map_err(|e| RepositoryError::Backend(e.to_string()))?
+            {
+                yield Self::row_to_item(&row)?;
+            }
+        };
+
+        Box::pin(stream)
+    }
+}
+
+/// Builds the repository selected by `DATABASE_URL`, falling back to the
+/// in-memory store when it's unset.
+pub async fn from_env() -> Arc<dyn Repository> {
+    match std::env::var("DATABASE_URL") {
+        Ok(url) => {
+            let repo = SqlxRepository::connect(&url)
+                .await
+                .expect("failed to connect to DATABASE_URL");
+            tracing::info!("using sqlx-backed item store");
+            Arc::new(repo)
+        }
+        Err(_) => {
+            tracing::info!("DATABASE_URL not set, using in-memory item store");
+            Arc::new(InMemoryRepository::new())
+        }
+    }
+}